@@ -2,7 +2,11 @@ use crate::datatypes::Int64Chunked;
 use crate::export::chrono::NaiveDateTime;
 use crate::prelude::*;
 use crate::prelude::{DatetimeChunked, TimeUnit};
-use polars_time::export::chrono::Datelike;
+use polars_time::export::chrono::{Datelike, Months, NaiveDate, TimeZone, Timelike};
+#[cfg(feature = "timezones")]
+use polars_time::export::chrono_tz::Tz;
+#[cfg(feature = "timezones")]
+use polars_time::export::chrono::LocalResult;
 pub use polars_time::*;
 
 pub fn in_nanoseconds_window(ndt: &NaiveDateTime) -> bool {
@@ -10,6 +14,47 @@ pub fn in_nanoseconds_window(ndt: &NaiveDateTime) -> bool {
     !(ndt.year() > 2554 || ndt.year() < 1386)
 }
 
+pub fn in_microseconds_window(ndt: &NaiveDateTime) -> bool {
+    // The i64 microsecond range spans ~292_277 years, wider than chrono's own representable range,
+    // so the effective bound is chrono's (~262_143 years around 1 CE).
+    !(ndt.year() > 262_143 || ndt.year() < -262_143)
+}
+
+/// Convert a timestamp in the given [`TimeUnit`] to a [`NaiveDateTime`].
+///
+/// Uses Euclidean division so the sub-second remainder is always non-negative — truncating `%`
+/// would feed a ~4.29e9 nanosecond value into chrono for pre-1970 timestamps and panic.
+fn timestamp_to_datetime(timestamp: i64, tu: TimeUnit) -> Result<NaiveDateTime> {
+    let (secs, nsec) = match tu {
+        TimeUnit::Nanoseconds => (
+            timestamp.div_euclid(1_000_000_000),
+            timestamp.rem_euclid(1_000_000_000) as u32,
+        ),
+        TimeUnit::Microseconds => (
+            timestamp.div_euclid(1_000_000),
+            (timestamp.rem_euclid(1_000_000) * 1_000) as u32,
+        ),
+        TimeUnit::Milliseconds => (
+            timestamp.div_euclid(1_000),
+            (timestamp.rem_euclid(1_000) * 1_000_000) as u32,
+        ),
+    };
+    NaiveDateTime::from_timestamp_opt(secs, nsec).ok_or_else(|| {
+        PolarsError::ComputeError(
+            format!("timestamp {} out of range for time unit {:?}", timestamp, tu).into(),
+        )
+    })
+}
+
+/// Convert a [`NaiveDateTime`] back to a timestamp in the given [`TimeUnit`].
+fn datetime_to_timestamp(ndt: NaiveDateTime, tu: TimeUnit) -> i64 {
+    match tu {
+        TimeUnit::Nanoseconds => ndt.timestamp_nanos(),
+        TimeUnit::Microseconds => ndt.timestamp() * 1_000_000 + ndt.timestamp_subsec_micros() as i64,
+        TimeUnit::Milliseconds => ndt.timestamp_millis(),
+    }
+}
+
 pub fn date_range(
     name: &str,
     start: i64,
@@ -17,12 +62,77 @@ pub fn date_range(
     every: Duration,
     closed: ClosedWindow,
     tu: TimeUnit,
-) -> DatetimeChunked {
-    Int64Chunked::new_vec(
-        name,
-        date_range_vec(start, stop, every, closed, tu.to_polars_time()),
-    )
-    .into_datetime(tu, None)
+    tz: Option<&str>,
+) -> Result<DatetimeChunked> {
+    match tz {
+        #[cfg(feature = "timezones")]
+        Some(tz) => {
+            let tz: Tz = tz.parse().map_err(|_| {
+                PolarsError::ComputeError(format!("unknown timezone: {}", tz).into())
+            })?;
+            let values = date_range_tz_vec(start, stop, every, closed, tu, tz)?;
+            Ok(Int64Chunked::new_vec(name, values).into_datetime(tu, Some(tz.to_string())))
+        }
+        #[cfg(not(feature = "timezones"))]
+        Some(_) => Err(PolarsError::ComputeError(
+            "activate 'timezones' feature to construct a timezone-aware date_range".into(),
+        )),
+        None => Ok(Int64Chunked::new_vec(
+            name,
+            date_range_vec(start, stop, every, closed, tu.to_polars_time()),
+        )
+        .into_datetime(tu, None)),
+    }
+}
+
+/// Resolve the wall-clock datetime `ndt` in `tz` to its UTC instant, as used by the local-time
+/// stepping of `date_range_tz_vec`.
+///
+/// Ambiguous local times (fall-back) resolve to the earliest valid instant; nonexistent local
+/// times (spring-forward) raise a [`PolarsError::ComputeError`].
+#[cfg(feature = "timezones")]
+fn localize(ndt: NaiveDateTime, tz: &Tz) -> Result<NaiveDateTime> {
+    match tz.from_local_datetime(&ndt) {
+        LocalResult::Single(dt) => Ok(dt.naive_utc()),
+        LocalResult::Ambiguous(earliest, _) => Ok(earliest.naive_utc()),
+        LocalResult::None => Err(PolarsError::ComputeError(
+            format!("local datetime {} does not exist in timezone {}", ndt, tz).into(),
+        )),
+    }
+}
+
+#[cfg(feature = "timezones")]
+fn date_range_tz_vec(
+    start: i64,
+    stop: i64,
+    every: Duration,
+    closed: ClosedWindow,
+    tu: TimeUnit,
+    tz: Tz,
+) -> Result<Vec<i64>> {
+    let mut out = Vec::new();
+    // Step in local wall-clock time so that e.g. "every 1d" keeps the same local hour across DST.
+    let mut wall = tz
+        .from_utc_datetime(&timestamp_to_datetime(start, tu)?)
+        .naive_local();
+    loop {
+        let instant = datetime_to_timestamp(localize(wall, &tz)?, tu);
+        let include = match closed {
+            ClosedWindow::Both | ClosedWindow::Left => instant >= start,
+            ClosedWindow::Right | ClosedWindow::None => instant > start,
+        } && match closed {
+            ClosedWindow::Both | ClosedWindow::Right => instant <= stop,
+            ClosedWindow::Left | ClosedWindow::None => instant < stop,
+        };
+        if instant > stop {
+            break;
+        }
+        if include {
+            out.push(instant);
+        }
+        wall = add_duration(wall, &every);
+    }
+    Ok(out)
 }
 
 impl DataFrame {
@@ -140,7 +250,7 @@ impl DataFrame {
 
         use DataType::*;
         match index_column.dtype() {
-            Datetime(tu, _) => {
+            Datetime(tu, tz) => {
                 let s = index_column.cast(&DataType::Int64).unwrap();
                 let ca = s.i64().unwrap();
                 let first = ca.into_iter().flatten().next();
@@ -149,12 +259,27 @@ impl DataFrame {
                     (Some(first), Some(last)) => {
                         let first = match tu {
                             TimeUnit::Milliseconds => offset.add_ms(first),
+                            // `Duration` only exposes `add_ms`/`add_ns`. Apply the offset in
+                            // millisecond space on the whole-ms part and carry the sub-ms
+                            // microseconds, so we never widen `first` by 1000 (which overflows i64
+                            // for far-future microsecond instants).
+                            TimeUnit::Microseconds => {
+                                offset.add_ms(first.div_euclid(1_000)) * 1_000
+                                    + first.rem_euclid(1_000)
+                            }
                             TimeUnit::Nanoseconds => offset.add_ns(first),
                         };
-                        let range =
-                            date_range(index_col_name, first, last, every, ClosedWindow::Both, *tu)
-                                .into_series()
-                                .into_frame();
+                        let range = date_range(
+                            index_col_name,
+                            first,
+                            last,
+                            every,
+                            ClosedWindow::Both,
+                            *tu,
+                            tz.as_deref(),
+                        )?
+                        .into_series()
+                        .into_frame();
                         range.join(
                             self,
                             &[index_col_name],
@@ -174,3 +299,1089 @@ impl DataFrame {
         }
     }
 }
+
+use polars_time::export::chrono::{Duration as ChronoDuration, Weekday};
+
+/// Recurrence frequency, mirroring the `FREQ` field of an iCalendar (RFC 5545) recurrence rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Freq {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A weekday with an optional ordinal, e.g. `-1FR` (the last Friday) or `2TU` (the second Tuesday)
+/// of the surrounding period.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NWeekday {
+    pub weekday: Weekday,
+    pub ordinal: Option<i32>,
+}
+
+impl NWeekday {
+    pub fn new(weekday: Weekday, ordinal: Option<i32>) -> Self {
+        NWeekday { weekday, ordinal }
+    }
+}
+
+/// A calendar recurrence rule, complementing [`date_range`] for patterns fixed-duration stepping
+/// cannot express (e.g. "the last business day of each month" or "every 2nd Tuesday").
+///
+/// The fields map onto the `BY*` parts of an RFC 5545 `RRULE`. For date-based frequencies, unset
+/// `BYHOUR`/`BYMINUTE`/`BYSECOND` take the seed's time of day, and when neither `BYDAY` nor
+/// `BYMONTHDAY` is given each `MONTHLY`/`YEARLY` period uses the seed's day of month (and, under
+/// `YEARLY` without `BYMONTH`, the seed's month) while `WEEKLY`/`DAILY` periods step from the seed's
+/// date; otherwise `BYDAY`/`BYMONTHDAY` expand over the whole period.
+#[derive(Clone, Debug)]
+pub struct RecurrenceRule {
+    pub freq: Freq,
+    pub interval: i64,
+    pub count: Option<usize>,
+    /// Inclusive upper bound, expressed in the same [`TimeUnit`] as the seed.
+    pub until: Option<i64>,
+    pub by_month: Vec<u32>,
+    pub by_month_day: Vec<i32>,
+    pub by_day: Vec<NWeekday>,
+    pub by_hour: Vec<u32>,
+    pub by_minute: Vec<u32>,
+    pub by_second: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+}
+
+impl RecurrenceRule {
+    pub fn new(freq: Freq) -> Self {
+        RecurrenceRule {
+            freq,
+            interval: 1,
+            count: None,
+            until: None,
+            by_month: Vec::new(),
+            by_month_day: Vec::new(),
+            by_day: Vec::new(),
+            by_hour: Vec::new(),
+            by_minute: Vec::new(),
+            by_second: Vec::new(),
+            by_set_pos: Vec::new(),
+        }
+    }
+}
+
+/// Pick elements of `candidates` (assumed sorted) by the 1-based `BYSETPOS` selectors, negative
+/// positions counting from the end.
+fn apply_set_pos(candidates: &[NaiveDateTime], set_pos: &[i32]) -> Vec<NaiveDateTime> {
+    if set_pos.is_empty() {
+        return candidates.to_vec();
+    }
+    let len = candidates.len() as i32;
+    let mut out = Vec::with_capacity(set_pos.len());
+    for &pos in set_pos {
+        let idx = if pos > 0 { pos - 1 } else { len + pos };
+        if idx >= 0 && idx < len {
+            out.push(candidates[idx as usize]);
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+/// Last day (28-31) of the month containing `year`/`month`.
+fn last_day_of_month(year: i32, month: u32) -> u32 {
+    let (ny, nm) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    (NaiveDate::from_ymd(ny, nm, 1) - ChronoDuration::days(1)).day()
+}
+
+/// Resolve a possibly-negative day-of-month against the length of the given month.
+fn resolve_month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    let last = last_day_of_month(year, month) as i32;
+    let d = if day > 0 { day } else { last + day + 1 };
+    if d >= 1 && d <= last {
+        NaiveDate::from_ymd_opt(year, month, d as u32)
+    } else {
+        None
+    }
+}
+
+/// All dates within `[start, end]` (inclusive) matching `nwd`, honoring its ordinal.
+fn weekday_dates_in_span(start: NaiveDate, end: NaiveDate, nwd: &NWeekday) -> Vec<NaiveDate> {
+    let mut hits = Vec::new();
+    let mut d = start;
+    while d <= end {
+        if d.weekday() == nwd.weekday {
+            hits.push(d);
+        }
+        d += ChronoDuration::days(1);
+    }
+    match nwd.ordinal {
+        None => hits,
+        Some(n) => {
+            let len = hits.len() as i32;
+            let idx = if n > 0 { n - 1 } else { len + n };
+            if idx >= 0 && idx < len {
+                vec![hits[idx as usize]]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Expand the candidate dates of a single period anchored at `anchor`, using the active `BY*` day
+/// and month sets, defaulting from `seed` where unset.
+///
+/// The span covers the *whole* period, so an ordinal `BYDAY` (e.g. `-1FR`) under `FREQ=YEARLY`
+/// resolves over the year rather than per month, per RFC 5545.
+fn expand_period_dates(
+    freq: Freq,
+    anchor: NaiveDate,
+    seed: &NaiveDateTime,
+    rule: &RecurrenceRule,
+) -> Vec<NaiveDate> {
+    let year = anchor.year();
+    let (span_start, span_end) = match freq {
+        Freq::Yearly => (
+            NaiveDate::from_ymd(year, 1, 1),
+            NaiveDate::from_ymd(year, 12, 31),
+        ),
+        Freq::Weekly => {
+            // ISO week (Mon..Sun) containing the anchor.
+            let offset = anchor.weekday().num_days_from_monday() as i64;
+            let s = anchor - ChronoDuration::days(offset);
+            (s, s + ChronoDuration::days(6))
+        }
+        Freq::Monthly => (
+            NaiveDate::from_ymd(year, anchor.month(), 1),
+            NaiveDate::from_ymd(year, anchor.month(), last_day_of_month(year, anchor.month())),
+        ),
+        Freq::Daily | Freq::Hourly | Freq::Minutely | Freq::Secondly => (anchor, anchor),
+    };
+
+    let mut dates = Vec::new();
+    if !rule.by_day.is_empty() {
+        for nwd in &rule.by_day {
+            dates.extend(weekday_dates_in_span(span_start, span_end, nwd));
+        }
+        // When BYMONTHDAY is given alongside BYDAY, RFC 5545 takes their intersection: keep only the
+        // weekday matches that also fall on a listed day-of-month.
+        if !rule.by_month_day.is_empty() {
+            dates.retain(|d| {
+                rule.by_month_day
+                    .iter()
+                    .any(|&md| resolve_month_day(d.year(), d.month(), md) == Some(*d))
+            });
+        }
+    } else if !rule.by_month_day.is_empty() {
+        // Expand the day-of-month constraint within every month the span touches.
+        let mut m = NaiveDate::from_ymd(span_start.year(), span_start.month(), 1);
+        while m <= span_end {
+            for &md in &rule.by_month_day {
+                if let Some(d) = resolve_month_day(m.year(), m.month(), md) {
+                    if d >= span_start && d <= span_end {
+                        dates.push(d);
+                    }
+                }
+            }
+            m += Months::new(1);
+        }
+    } else if !rule.by_month.is_empty() && freq == Freq::Yearly {
+        // BYMONTH alone under YEARLY: the seed's day in each listed month.
+        for &mo in &rule.by_month {
+            if let Some(d) = NaiveDate::from_ymd_opt(year, mo, seed.day()) {
+                dates.push(d);
+            }
+        }
+    } else {
+        match freq {
+            // The anchor's day can drift after month arithmetic clamps it (Jan 31 -> Feb 28 ->
+            // Mar 28), so take the seed's day in the anchor's month; months too short are skipped.
+            Freq::Monthly | Freq::Yearly => {
+                if let Some(d) = NaiveDate::from_ymd_opt(year, anchor.month(), seed.day()) {
+                    dates.push(d);
+                }
+            }
+            // Default to the anchor day itself.
+            _ => dates.push(anchor),
+        }
+    }
+
+    // BYMONTH narrows the expanded set when other BY* rules produced the candidates.
+    if !rule.by_month.is_empty() {
+        dates.retain(|d| rule.by_month.contains(&d.month()));
+    }
+    dates.sort_unstable();
+    dates.dedup();
+    dates
+}
+
+/// Generate the instants of `rule` starting from `seed`, within the half-open window `[seed, stop)`
+/// (and the rule's own `UNTIL`/`COUNT` bounds). Returns an [`Int64Chunked`] in `tu`, ready for
+/// [`Int64Chunked::into_datetime`].
+pub fn recurrence(
+    name: &str,
+    seed: i64,
+    stop: i64,
+    rule: &RecurrenceRule,
+    tu: TimeUnit,
+) -> Result<Int64Chunked> {
+    if rule.interval < 1 {
+        return Err(PolarsError::ComputeError(
+            "recurrence INTERVAL must be a positive integer".into(),
+        ));
+    }
+    let seed_dt = timestamp_to_datetime(seed, tu)?;
+
+    // Sub-day frequencies step a wall-clock cursor by the fixed increment and treat the BY* sets as
+    // filters, rather than expanding a date period.
+    if matches!(rule.freq, Freq::Hourly | Freq::Minutely | Freq::Secondly) {
+        return Ok(recurrence_subday(name, seed, stop, rule, tu, seed_dt));
+    }
+
+    let until = rule.until;
+    let hours: Vec<u32> = if rule.by_hour.is_empty() {
+        vec![seed_dt.hour()]
+    } else {
+        rule.by_hour.clone()
+    };
+    let minutes: Vec<u32> = if rule.by_minute.is_empty() {
+        vec![seed_dt.minute()]
+    } else {
+        rule.by_minute.clone()
+    };
+    let seconds: Vec<u32> = if rule.by_second.is_empty() {
+        vec![seed_dt.second()]
+    } else {
+        rule.by_second.clone()
+    };
+
+    let mut out = Vec::new();
+    let mut anchor = seed_dt.date();
+    // A generous bound so a rule that never fires (no candidate survives) cannot loop forever. The
+    // loop normally terminates when the period cursor passes `stop` or a COUNT/UNTIL bound is hit;
+    // exhausting this cap means the caller asked for an effectively unbounded rule, which we surface
+    // rather than silently truncate.
+    let max_periods = 1_000_000usize;
+    let mut exhausted = true;
+    for _ in 0..max_periods {
+        let dates = expand_period_dates(rule.freq, anchor, &seed_dt, rule);
+        let mut candidates = Vec::new();
+        for d in &dates {
+            for &h in &hours {
+                for &m in &minutes {
+                    for &s in &seconds {
+                        if let Some(t) = d.and_hms_opt(h, m, s) {
+                            candidates.push(t);
+                        }
+                    }
+                }
+            }
+        }
+        candidates.sort_unstable();
+        for instant in apply_set_pos(&candidates, &rule.by_set_pos) {
+            let ts = datetime_to_timestamp(instant, tu);
+            if ts < seed {
+                continue;
+            }
+            if ts >= stop {
+                return Ok(Int64Chunked::new_vec(name, out));
+            }
+            if let Some(until) = until {
+                if ts > until {
+                    return Ok(Int64Chunked::new_vec(name, out));
+                }
+            }
+            out.push(ts);
+            if let Some(count) = rule.count {
+                if out.len() >= count {
+                    return Ok(Int64Chunked::new_vec(name, out));
+                }
+            }
+        }
+        anchor = advance_period(anchor, rule.freq, rule.interval);
+        // Compare the start of the next period, not the anchor: candidates can precede the anchor
+        // within its period (e.g. BYDAY expanded from the first of the month).
+        let period_start = match rule.freq {
+            Freq::Yearly => NaiveDate::from_ymd(anchor.year(), 1, 1),
+            Freq::Monthly => NaiveDate::from_ymd(anchor.year(), anchor.month(), 1),
+            Freq::Weekly => {
+                anchor - ChronoDuration::days(anchor.weekday().num_days_from_monday() as i64)
+            }
+            _ => anchor,
+        };
+        if datetime_to_timestamp(period_start.and_hms(0, 0, 0), tu) >= stop {
+            exhausted = false;
+            break;
+        }
+    }
+    if exhausted {
+        return Err(PolarsError::ComputeError(
+            "recurrence exceeded the period limit; bound the rule with a reachable stop, UNTIL or COUNT".into(),
+        ));
+    }
+    Ok(Int64Chunked::new_vec(name, out))
+}
+
+/// Does `dt` satisfy the non-frequency `BY*` filters of `rule`? Ordinals on `BYDAY` are ignored
+/// here (they only make sense against a bounded period); the weekday alone must match.
+fn passes_byfilters(dt: &NaiveDateTime, rule: &RecurrenceRule) -> bool {
+    if !rule.by_month.is_empty() && !rule.by_month.contains(&dt.month()) {
+        return false;
+    }
+    if !rule.by_month_day.is_empty() {
+        let last = last_day_of_month(dt.year(), dt.month()) as i32;
+        let day = dt.day() as i32;
+        if !rule
+            .by_month_day
+            .iter()
+            .any(|&md| if md > 0 { md == day } else { last + md + 1 == day })
+        {
+            return false;
+        }
+    }
+    if !rule.by_day.is_empty() && !rule.by_day.iter().any(|nwd| nwd.weekday == dt.weekday()) {
+        return false;
+    }
+    if !rule.by_hour.is_empty() && !rule.by_hour.contains(&dt.hour()) {
+        return false;
+    }
+    if !rule.by_minute.is_empty() && !rule.by_minute.contains(&dt.minute()) {
+        return false;
+    }
+    if !rule.by_second.is_empty() && !rule.by_second.contains(&dt.second()) {
+        return false;
+    }
+    true
+}
+
+/// Stepping loop for `FREQ=HOURLY|MINUTELY|SECONDLY`: advance the cursor by the fixed increment and
+/// keep the instants passing the `BY*` filters, honoring the `UNTIL`/`COUNT` bounds and `[seed, stop)`.
+fn recurrence_subday(
+    name: &str,
+    seed: i64,
+    stop: i64,
+    rule: &RecurrenceRule,
+    tu: TimeUnit,
+    seed_dt: NaiveDateTime,
+) -> Int64Chunked {
+    let increment = match rule.freq {
+        Freq::Hourly => ChronoDuration::hours(rule.interval),
+        Freq::Minutely => ChronoDuration::minutes(rule.interval),
+        Freq::Secondly => ChronoDuration::seconds(rule.interval),
+        _ => unreachable!("recurrence_subday only handles sub-day frequencies"),
+    };
+    let mut out = Vec::new();
+    let mut cur = seed_dt;
+    loop {
+        let ts = datetime_to_timestamp(cur, tu);
+        if ts >= stop {
+            break;
+        }
+        if let Some(until) = rule.until {
+            if ts > until {
+                break;
+            }
+        }
+        if ts >= seed && passes_byfilters(&cur, rule) {
+            out.push(ts);
+            if let Some(count) = rule.count {
+                if out.len() >= count {
+                    break;
+                }
+            }
+        }
+        cur += increment;
+    }
+    Int64Chunked::new_vec(name, out)
+}
+
+/// Advance the period anchor by `FREQ * interval` (date-based frequencies only; sub-day
+/// frequencies are stepped by [`recurrence_subday`]).
+fn advance_period(anchor: NaiveDate, freq: Freq, interval: i64) -> NaiveDate {
+    match freq {
+        Freq::Yearly => {
+            if interval >= 0 {
+                anchor + Months::new((interval * 12) as u32)
+            } else {
+                anchor - Months::new((-interval * 12) as u32)
+            }
+        }
+        Freq::Monthly => {
+            if interval >= 0 {
+                anchor + Months::new(interval as u32)
+            } else {
+                anchor - Months::new((-interval) as u32)
+            }
+        }
+        Freq::Weekly => anchor + ChronoDuration::days(7 * interval),
+        Freq::Daily | Freq::Hourly | Freq::Minutely | Freq::Secondly => {
+            anchor + ChronoDuration::days(interval)
+        }
+    }
+}
+
+/// Add the calendar-aware part (months) then the fixed part of `every` to a naive datetime.
+fn add_duration(ndt: NaiveDateTime, every: &Duration) -> NaiveDateTime {
+    let mut out = ndt;
+    let months = every.months();
+    if months != 0 {
+        out = if months >= 0 {
+            out + Months::new(months as u32)
+        } else {
+            out - Months::new((-months) as u32)
+        };
+    }
+    out + ChronoDuration::nanoseconds(every.duration_ns())
+}
+
+/// Truncate `ndt` down to the calendar unit expressed by `every`.
+///
+/// Month- and year-sized units reset the day and time-of-day components (rather than merely
+/// subtracting a fixed number of milliseconds); sub-month units floor to a multiple of the unit
+/// measured from the Unix epoch.
+pub fn date_floor(ndt: NaiveDateTime, every: &Duration) -> NaiveDateTime {
+    let months = every.months();
+    if months != 0 {
+        let total = ndt.year() as i64 * 12 + (ndt.month() as i64 - 1);
+        let snapped = total - total.rem_euclid(months);
+        let year = snapped.div_euclid(12) as i32;
+        let month = (snapped.rem_euclid(12) + 1) as u32;
+        NaiveDate::from_ymd(year, month, 1).and_hms(0, 0, 0)
+    } else {
+        // Round the fixed part down to a multiple of the step by subtracting the (non-negative)
+        // remainder directly. Compute the epoch offset in i128 so far-dated (but ms/µs-valid)
+        // instants don't overflow `timestamp_nanos()`; the remainder is < step and fits i64.
+        let step = every.duration_ns();
+        let rem = epoch_nanos_i128(ndt).rem_euclid(step as i128) as i64;
+        ndt - ChronoDuration::nanoseconds(rem)
+    }
+}
+
+/// Nanoseconds since the Unix epoch as an `i128`, avoiding the overflow `timestamp_nanos()` hits
+/// for instants outside chrono's ~1678–2262 nanosecond window.
+fn epoch_nanos_i128(ndt: NaiveDateTime) -> i128 {
+    ndt.timestamp() as i128 * 1_000_000_000 + ndt.timestamp_subsec_nanos() as i128
+}
+
+/// Truncate `ndt` up to the calendar unit expressed by `every`. Already-aligned instants are
+/// returned unchanged.
+pub fn date_ceil(ndt: NaiveDateTime, every: &Duration) -> NaiveDateTime {
+    let floored = date_floor(ndt, every);
+    if floored == ndt {
+        floored
+    } else {
+        add_duration(floored, every)
+    }
+}
+
+/// Candidate "nice" breakpoint steps, coarsest last, as (parse string, approximate nanoseconds).
+fn nice_steps() -> Vec<(&'static str, i64)> {
+    const S: i64 = 1_000_000_000;
+    const MIN: i64 = 60 * S;
+    const H: i64 = 60 * MIN;
+    const D: i64 = 24 * H;
+    const MO: i64 = 2_629_746 * S; // average Gregorian month
+    const Y: i64 = 31_556_952 * S; // average Gregorian year
+    vec![
+        ("1s", S),
+        ("2s", 2 * S),
+        ("5s", 5 * S),
+        ("10s", 10 * S),
+        ("15s", 15 * S),
+        ("30s", 30 * S),
+        ("1m", MIN),
+        ("2m", 2 * MIN),
+        ("5m", 5 * MIN),
+        ("10m", 10 * MIN),
+        ("15m", 15 * MIN),
+        ("30m", 30 * MIN),
+        ("1h", H),
+        ("2h", 2 * H),
+        ("3h", 3 * H),
+        ("6h", 6 * H),
+        ("12h", 12 * H),
+        ("1d", D),
+        ("2d", 2 * D),
+        ("1w", 7 * D),
+        ("1mo", MO),
+        ("3mo", 3 * MO),
+        ("6mo", 6 * MO),
+        ("1y", Y),
+        ("2y", 2 * Y),
+        ("5y", 5 * Y),
+        ("10y", 10 * Y),
+    ]
+}
+
+/// A [`date_range`] variant that snaps `[start, stop]` to "nice" calendar boundaries and picks a
+/// readable step yielding roughly `target` breakpoints — for axis ticks or binning edges rather
+/// than raw every-N ranges.
+pub fn date_range_nice(
+    name: &str,
+    start: i64,
+    stop: i64,
+    target: usize,
+    tu: TimeUnit,
+) -> Result<DatetimeChunked> {
+    if stop <= start || target == 0 {
+        return Err(PolarsError::ComputeError(
+            "date_range_nice requires start < stop and a positive target".into(),
+        ));
+    }
+    // Measure the span in i128 nanoseconds so far-dated inputs don't overflow the conversion; the
+    // step selection only needs it as an `f64` ratio.
+    let start_ns = epoch_nanos_i128(timestamp_to_datetime(start, tu)?);
+    let stop_ns = epoch_nanos_i128(timestamp_to_datetime(stop, tu)?);
+    let span = (stop_ns - start_ns) as f64;
+
+    // Pick the step whose breakpoint count is closest to the target, preferring the coarsest on a
+    // tie so grids stay readable.
+    let mut best: Option<(&'static str, i64)> = None;
+    for (s, step_ns) in nice_steps() {
+        let count = (span / step_ns as f64).max(1.0);
+        let score = (count - target as f64).abs();
+        match best {
+            Some((_, bs)) => {
+                let bcount = (span / bs as f64).max(1.0);
+                if score <= (bcount - target as f64).abs() {
+                    best = Some((s, step_ns));
+                }
+            }
+            None => best = Some((s, step_ns)),
+        }
+    }
+    let every = Duration::parse(best.unwrap().0);
+
+    let from = date_floor(timestamp_to_datetime(start, tu)?, &every);
+    let to = date_ceil(timestamp_to_datetime(stop, tu)?, &every);
+
+    let mut values = Vec::new();
+    let mut cur = from;
+    while cur <= to {
+        values.push(datetime_to_timestamp(cur, tu));
+        cur = add_duration(cur, &every);
+    }
+    Ok(Int64Chunked::new_vec(name, values).into_datetime(tu, None))
+}
+
+use rand::distributions::Uniform;
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+
+/// Fill a [`DatetimeChunked`] of length `size` with instants drawn uniformly at random from the
+/// raw i64 range `[low, high]` in the given [`TimeUnit`].
+///
+/// For [`TimeUnit::Nanoseconds`] and [`TimeUnit::Microseconds`] the bounds are first clamped to the
+/// representable window (see [`in_nanoseconds_window`]/[`in_microseconds_window`]) so the samples
+/// always round-trip through chrono. Pass a `seed` for reproducible draws; `None` seeds from entropy.
+pub fn random_datetime(
+    name: &str,
+    size: usize,
+    low: i64,
+    high: i64,
+    tu: TimeUnit,
+    seed: Option<u64>,
+) -> Result<DatetimeChunked> {
+    let (lo_bound, hi_bound) = representable_bounds(tu);
+    let (low, high) = (low.clamp(lo_bound, hi_bound), high.clamp(lo_bound, hi_bound));
+    if high < low {
+        return Err(PolarsError::ComputeError(
+            "random_datetime requires low <= high".into(),
+        ));
+    }
+
+    let mut rng = match seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_entropy(),
+    };
+    // Draw a Julian-day-style point uniformly across the raw i64 span; the time-of-day falls out of
+    // the same uniform draw.
+    let dist = Uniform::new_inclusive(low, high);
+    let values: Vec<i64> = (0..size).map(|_| dist.sample(&mut rng)).collect();
+    Ok(Int64Chunked::new_vec(name, values).into_datetime(tu, None))
+}
+
+/// Inclusive raw-i64 bounds of the window each [`TimeUnit`] can round-trip through chrono, inside
+/// [`in_nanoseconds_window`]/[`in_microseconds_window`]. Nanoseconds are bounded to 1678..=2261,
+/// the whole years an i64 nanosecond count can hold. Milliseconds are not clamped: the whole i64
+/// range is returned, of which chrono only represents about ±262_000 years.
+fn representable_bounds(tu: TimeUnit) -> (i64, i64) {
+    match tu {
+        TimeUnit::Milliseconds => (i64::MIN, i64::MAX),
+        TimeUnit::Nanoseconds => (
+            datetime_to_timestamp(NaiveDate::from_ymd(1678, 1, 1).and_hms(0, 0, 0), tu),
+            datetime_to_timestamp(NaiveDate::from_ymd(2261, 12, 31).and_hms(23, 59, 59), tu),
+        ),
+        TimeUnit::Microseconds => (
+            datetime_to_timestamp(NaiveDate::from_ymd(-262_143, 1, 1).and_hms(0, 0, 0), tu),
+            datetime_to_timestamp(NaiveDate::from_ymd(262_143, 12, 31).and_hms(23, 59, 59), tu),
+        ),
+    }
+}
+
+/// The difference between two datetimes broken into calendar components, decomposed the way a human
+/// would read it: whole years, then whole months (borrowing from the day field across the varying
+/// 28-31 day months), then the remaining days and time-of-day. All fields are non-negative and
+/// describe the larger-minus-smaller interval.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CalendarDiff {
+    pub years: i64,
+    pub months: i64,
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+    pub nanoseconds: i64,
+}
+
+/// Decompose `|b - a|` into calendar components. E.g. Jan 31 → Mar 1 reports 1 month and 1 day,
+/// matching calendar intuition rather than a flat 29/30-day duration.
+pub fn calendar_diff(a: NaiveDateTime, b: NaiveDateTime) -> CalendarDiff {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+
+    // Count whole months by walking forward from `a` without overshooting `b`; month arithmetic
+    // clamps to the last valid day, which is what makes the day field borrow correctly.
+    let mut total_months = (b.year() as i64 - a.year() as i64) * 12
+        + (b.month() as i64 - a.month() as i64);
+    let mut anchor = a + Months::new(total_months as u32);
+    if anchor > b {
+        total_months -= 1;
+        anchor = a + Months::new(total_months as u32);
+    }
+
+    let rem = b - anchor;
+    let days = rem.num_days();
+    let after_days = rem - ChronoDuration::days(days);
+    let hours = after_days.num_hours();
+    let minutes = after_days.num_minutes() - hours * 60;
+    let seconds = after_days.num_seconds() - after_days.num_minutes() * 60;
+    let nanoseconds =
+        after_days.num_nanoseconds().unwrap_or(0) - after_days.num_seconds() * 1_000_000_000;
+
+    CalendarDiff {
+        years: total_months / 12,
+        months: total_months % 12,
+        days,
+        hours,
+        minutes,
+        seconds,
+        nanoseconds,
+    }
+}
+
+/// Element-wise calendar-component difference between two Datetime columns.
+///
+/// Returns one `Series` per component (`years`, `months`, `days`, `hours`, `minutes`, `seconds`,
+/// `nanoseconds`); rows where either input is null are null throughout. The columns drop straight
+/// into `StructChunked::new`/`DataFrame::new` at the call site, keeping this layer free of the
+/// `dtype-struct` feature. See [`calendar_diff`] for the decomposition rules.
+pub fn datetime_calendar_diff(a: &Series, b: &Series) -> Result<Vec<Series>> {
+    let tu_a = match a.dtype() {
+        DataType::Datetime(tu, _) => *tu,
+        dt => {
+            return Err(PolarsError::ComputeError(
+                format!("datetime_calendar_diff expects Datetime columns, got {:?}", dt).into(),
+            ))
+        }
+    };
+    let tu_b = match b.dtype() {
+        DataType::Datetime(tu, _) => *tu,
+        dt => {
+            return Err(PolarsError::ComputeError(
+                format!("datetime_calendar_diff expects Datetime columns, got {:?}", dt).into(),
+            ))
+        }
+    };
+
+    let ca_a = a.cast(&DataType::Int64)?;
+    let ca_b = b.cast(&DataType::Int64)?;
+    let (ca_a, ca_b) = (ca_a.i64()?, ca_b.i64()?);
+
+    let n = ca_a.len();
+    let mut years = Vec::with_capacity(n);
+    let mut months = Vec::with_capacity(n);
+    let mut days = Vec::with_capacity(n);
+    let mut hours = Vec::with_capacity(n);
+    let mut minutes = Vec::with_capacity(n);
+    let mut seconds = Vec::with_capacity(n);
+    let mut nanoseconds = Vec::with_capacity(n);
+
+    for (va, vb) in ca_a.into_iter().zip(ca_b.into_iter()) {
+        match (va, vb) {
+            (Some(va), Some(vb)) => {
+                let diff = calendar_diff(
+                    timestamp_to_datetime(va, tu_a)?,
+                    timestamp_to_datetime(vb, tu_b)?,
+                );
+                years.push(Some(diff.years));
+                months.push(Some(diff.months));
+                days.push(Some(diff.days));
+                hours.push(Some(diff.hours));
+                minutes.push(Some(diff.minutes));
+                seconds.push(Some(diff.seconds));
+                nanoseconds.push(Some(diff.nanoseconds));
+            }
+            _ => {
+                years.push(None);
+                months.push(None);
+                days.push(None);
+                hours.push(None);
+                minutes.push(None);
+                seconds.push(None);
+                nanoseconds.push(None);
+            }
+        }
+    }
+
+    let col = |name: &str, v: Vec<Option<i64>>| {
+        let ca: Int64Chunked = v.into_iter().collect();
+        let mut s = ca.into_series();
+        s.rename(name);
+        s
+    };
+    Ok(vec![
+        col("years", years),
+        col("months", months),
+        col("days", days),
+        col("hours", hours),
+        col("minutes", minutes),
+        col("seconds", seconds),
+        col("nanoseconds", nanoseconds),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_to_datetime_pre_epoch() {
+        // A negative timestamp must not panic: truncating `%` would hand chrono a ~4.29e9 nsec.
+        let dt = timestamp_to_datetime(-1, TimeUnit::Milliseconds).unwrap();
+        assert_eq!(dt, NaiveDate::from_ymd(1969, 12, 31).and_hms_milli(23, 59, 59, 999));
+
+        let dt = timestamp_to_datetime(-1, TimeUnit::Nanoseconds).unwrap();
+        assert_eq!(dt.timestamp_nanos(), -1);
+
+        let dt = timestamp_to_datetime(-1, TimeUnit::Microseconds).unwrap();
+        assert_eq!(dt, NaiveDate::from_ymd(1969, 12, 31).and_hms_micro(23, 59, 59, 999_999));
+    }
+
+    #[cfg(feature = "timezones")]
+    #[test]
+    fn test_date_range_dst_keeps_wallclock() {
+        // Spring-forward in US/Eastern is 2021-03-14 02:00 -> 03:00 local. A daily step seeded at
+        // local midnight must stay at local midnight on both sides of the transition.
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let start = tz
+            .from_local_datetime(&NaiveDate::from_ymd(2021, 3, 13).and_hms(0, 0, 0))
+            .unwrap()
+            .naive_utc()
+            .timestamp_millis();
+        let stop = tz
+            .from_local_datetime(&NaiveDate::from_ymd(2021, 3, 15).and_hms(0, 0, 0))
+            .unwrap()
+            .naive_utc()
+            .timestamp_millis();
+        let ca = date_range(
+            "t",
+            start,
+            stop,
+            Duration::parse("1d"),
+            ClosedWindow::Both,
+            TimeUnit::Milliseconds,
+            Some("America/New_York"),
+        )
+        .unwrap();
+        let s = ca.cast(&DataType::Int64).unwrap();
+        let instants: Vec<i64> = s.i64().unwrap().into_iter().flatten().collect();
+        assert_eq!(instants.len(), 3);
+        for ts in instants {
+            let local = tz
+                .from_utc_datetime(&timestamp_to_datetime(ts, TimeUnit::Milliseconds).unwrap())
+                .naive_local();
+            assert_eq!((local.hour(), local.minute()), (0, 0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_recurrence {
+    use super::*;
+
+    fn ms(ndt: NaiveDateTime) -> i64 {
+        ndt.timestamp_millis()
+    }
+
+    fn instants(ca: &Int64Chunked) -> Vec<NaiveDateTime> {
+        ca.into_iter()
+            .flatten()
+            .map(|t| timestamp_to_datetime(t, TimeUnit::Milliseconds).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_last_business_day_of_month() {
+        let mut rule = RecurrenceRule::new(Freq::Monthly);
+        rule.by_day = vec![
+            NWeekday::new(Weekday::Mon, None),
+            NWeekday::new(Weekday::Tue, None),
+            NWeekday::new(Weekday::Wed, None),
+            NWeekday::new(Weekday::Thu, None),
+            NWeekday::new(Weekday::Fri, None),
+        ];
+        rule.by_set_pos = vec![-1];
+        let seed = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0));
+        let stop = ms(NaiveDate::from_ymd(2021, 4, 1).and_hms(0, 0, 0));
+        let got = instants(&recurrence("t", seed, stop, &rule, TimeUnit::Milliseconds).unwrap());
+        assert_eq!(
+            got,
+            vec![
+                NaiveDate::from_ymd(2021, 1, 29).and_hms(0, 0, 0),
+                NaiveDate::from_ymd(2021, 2, 26).and_hms(0, 0, 0),
+                NaiveDate::from_ymd(2021, 3, 31).and_hms(0, 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yearly_last_friday_scoped_to_year() {
+        let mut rule = RecurrenceRule::new(Freq::Yearly);
+        rule.by_day = vec![NWeekday::new(Weekday::Fri, Some(-1))];
+        let seed = ms(NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0));
+        let stop = ms(NaiveDate::from_ymd(2023, 1, 1).and_hms(0, 0, 0));
+        let got = instants(&recurrence("t", seed, stop, &rule, TimeUnit::Milliseconds).unwrap());
+        // One per year, not one per month.
+        assert_eq!(got.len(), 3);
+        for d in got {
+            assert_eq!(d.weekday(), Weekday::Fri);
+            assert_eq!(d.month(), 12);
+        }
+    }
+
+    #[test]
+    fn test_hourly_interval_steps_by_hours() {
+        let mut rule = RecurrenceRule::new(Freq::Hourly);
+        rule.interval = 2;
+        let seed = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0));
+        let stop = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(12, 0, 0));
+        let got = instants(&recurrence("t", seed, stop, &rule, TimeUnit::Milliseconds).unwrap());
+        let hours: Vec<u32> = got.iter().map(|d| d.hour()).collect();
+        assert_eq!(hours, vec![0, 2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn test_monthly_byday_intersected_with_bymonthday() {
+        // "Friday the 13th": the weekday and day-of-month constraints must be intersected, not
+        // treated as alternatives.
+        let mut rule = RecurrenceRule::new(Freq::Monthly);
+        rule.by_day = vec![NWeekday::new(Weekday::Fri, None)];
+        rule.by_month_day = vec![13];
+        let seed = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0));
+        let stop = ms(NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0));
+        let got = instants(&recurrence("t", seed, stop, &rule, TimeUnit::Milliseconds).unwrap());
+        assert_eq!(got, vec![NaiveDate::from_ymd(2021, 8, 13).and_hms(0, 0, 0)]);
+    }
+
+    #[test]
+    fn test_unbounded_rule_errors_rather_than_truncating() {
+        // No reachable stop, UNTIL or COUNT: the generator must refuse rather than silently cap.
+        let rule = RecurrenceRule::new(Freq::Daily);
+        let seed = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0));
+        assert!(recurrence("t", seed, i64::MAX, &rule, TimeUnit::Milliseconds).is_err());
+    }
+
+    #[test]
+    fn test_secondly_with_bysecond_filter() {
+        let mut rule = RecurrenceRule::new(Freq::Secondly);
+        rule.by_second = vec![0, 30];
+        let seed = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0));
+        let stop = ms(NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 2, 0));
+        let got = instants(&recurrence("t", seed, stop, &rule, TimeUnit::Milliseconds).unwrap());
+        let secs: Vec<u32> = got.iter().map(|d| d.second()).collect();
+        assert_eq!(secs, vec![0, 30, 0, 30]);
+    }
+}
+
+#[cfg(test)]
+mod test_microseconds {
+    use super::*;
+
+    #[test]
+    fn test_microsecond_roundtrip_preserves_precision() {
+        // A microsecond-precision instant must not widen to ns or truncate to ms.
+        let ndt = NaiveDate::from_ymd(2022, 6, 15).and_hms_micro(12, 30, 45, 123_456);
+        let ts = datetime_to_timestamp(ndt, TimeUnit::Microseconds);
+        assert_eq!(timestamp_to_datetime(ts, TimeUnit::Microseconds).unwrap(), ndt);
+    }
+
+    #[test]
+    fn test_upsample_microseconds_far_future_no_overflow() {
+        // A far-future microsecond instant whose value widened to nanoseconds (×1000) would
+        // overflow i64; the upsample offset must be applied in millisecond space instead.
+        let base = NaiveDate::from_ymd(5000, 1, 1).and_hms(0, 0, 0);
+        let ts = base.timestamp() * 1_000_000;
+        let s = Int64Chunked::new_vec("t", vec![ts, ts + 3_600_000_000])
+            .into_datetime(TimeUnit::Microseconds, None)
+            .into_series();
+        let df = DataFrame::new(vec![s]).unwrap();
+        let out = df
+            .upsample(
+                Vec::<String>::new(),
+                "t",
+                Duration::parse("1h"),
+                Duration::parse("0ns"),
+            )
+            .unwrap();
+        assert!(out.height() >= 2);
+    }
+
+    #[test]
+    fn test_random_datetime_microseconds_in_window() {
+        let ca = random_datetime("t", 64, i64::MIN, i64::MAX, TimeUnit::Microseconds, Some(0))
+            .unwrap();
+        let s = ca.cast(&DataType::Int64).unwrap();
+        for ts in s.i64().unwrap().into_iter().flatten() {
+            let ndt = timestamp_to_datetime(ts, TimeUnit::Microseconds).unwrap();
+            assert!(in_microseconds_window(&ndt));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_calendar_diff {
+    use super::*;
+
+    #[test]
+    fn test_jan31_to_mar1_is_one_month_one_day() {
+        let a = NaiveDate::from_ymd(2021, 1, 31).and_hms(0, 0, 0);
+        let b = NaiveDate::from_ymd(2021, 3, 1).and_hms(0, 0, 0);
+        let diff = calendar_diff(a, b);
+        assert_eq!((diff.years, diff.months, diff.days), (0, 1, 1));
+    }
+
+    #[test]
+    fn test_diff_is_symmetric_in_magnitude() {
+        let a = NaiveDate::from_ymd(1990, 6, 15).and_hms(8, 30, 0);
+        let b = NaiveDate::from_ymd(2023, 2, 1).and_hms(12, 0, 0);
+        assert_eq!(calendar_diff(a, b), calendar_diff(b, a));
+    }
+
+    #[test]
+    fn test_leap_day_whole_years() {
+        // 2020-02-29 -> 2024-02-29 is exactly 4 years, 0 months, 0 days.
+        let a = NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0);
+        let b = NaiveDate::from_ymd(2024, 2, 29).and_hms(0, 0, 0);
+        let diff = calendar_diff(a, b);
+        assert_eq!(
+            (diff.years, diff.months, diff.days, diff.hours),
+            (4, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_time_of_day_components() {
+        let a = NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0);
+        let b = NaiveDate::from_ymd(2021, 1, 1).and_hms_nano(1, 2, 3, 4);
+        let diff = calendar_diff(a, b);
+        assert_eq!(
+            (diff.hours, diff.minutes, diff.seconds, diff.nanoseconds),
+            (1, 2, 3, 4)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_nice {
+    use super::*;
+
+    #[test]
+    fn test_date_floor_month_resets_day_and_time() {
+        let ndt = NaiveDate::from_ymd(2021, 3, 15).and_hms(12, 34, 56);
+        let floored = date_floor(ndt, &Duration::parse("1mo"));
+        assert_eq!(floored, NaiveDate::from_ymd(2021, 3, 1).and_hms(0, 0, 0));
+        let ceiled = date_ceil(ndt, &Duration::parse("1mo"));
+        assert_eq!(ceiled, NaiveDate::from_ymd(2021, 4, 1).and_hms(0, 0, 0));
+    }
+
+    #[test]
+    fn test_date_floor_year_resets_to_jan_first() {
+        let ndt = NaiveDate::from_ymd(2021, 3, 15).and_hms(12, 0, 0);
+        let floored = date_floor(ndt, &Duration::parse("1y"));
+        assert_eq!(floored, NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0));
+    }
+
+    #[test]
+    fn test_date_range_nice_snaps_to_month_grid() {
+        let start = NaiveDate::from_ymd(2021, 1, 15).and_hms(6, 0, 0).timestamp_millis();
+        let stop = NaiveDate::from_ymd(2021, 6, 20).and_hms(9, 0, 0).timestamp_millis();
+        let ca = date_range_nice("t", start, stop, 6, TimeUnit::Milliseconds).unwrap();
+        let s = ca.cast(&DataType::Int64).unwrap();
+        let instants: Vec<NaiveDateTime> = s
+            .i64()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|t| timestamp_to_datetime(t, TimeUnit::Milliseconds).unwrap())
+            .collect();
+        assert!(instants.len() >= 2);
+        // Every breakpoint lands on a month boundary, covering the whole span.
+        for d in &instants {
+            assert_eq!((d.day(), d.hour(), d.minute()), (1, 0, 0));
+        }
+        assert!(instants.first().unwrap() <= &timestamp_to_datetime(start, TimeUnit::Milliseconds).unwrap());
+        assert!(instants.last().unwrap() >= &timestamp_to_datetime(stop, TimeUnit::Milliseconds).unwrap());
+    }
+
+    #[test]
+    fn test_date_floor_beyond_nanosecond_window() {
+        // Year 5000 is far outside chrono's ~1678–2262 nanosecond range but still valid in
+        // milliseconds; flooring to an hour must not overflow the epoch conversion.
+        let ndt = NaiveDate::from_ymd(5000, 7, 4).and_hms(13, 27, 45);
+        let floored = date_floor(ndt, &Duration::parse("1h"));
+        assert_eq!(floored, NaiveDate::from_ymd(5000, 7, 4).and_hms(13, 0, 0));
+    }
+
+    #[test]
+    fn test_date_range_nice_beyond_nanosecond_window() {
+        let start = NaiveDate::from_ymd(5000, 1, 10).and_hms(0, 0, 0).timestamp_millis();
+        let stop = NaiveDate::from_ymd(5000, 8, 10).and_hms(0, 0, 0).timestamp_millis();
+        let ca = date_range_nice("t", start, stop, 6, TimeUnit::Milliseconds).unwrap();
+        assert!(ca.len() >= 2);
+    }
+}
+
+#[cfg(test)]
+mod test_random {
+    use super::*;
+
+    fn raw(ca: &DatetimeChunked) -> Vec<i64> {
+        ca.cast(&DataType::Int64)
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn test_random_datetime_seed_is_reproducible() {
+        let low = NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let high = NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let a = random_datetime("t", 128, low, high, TimeUnit::Milliseconds, Some(42)).unwrap();
+        let b = random_datetime("t", 128, low, high, TimeUnit::Milliseconds, Some(42)).unwrap();
+        assert_eq!(raw(&a), raw(&b));
+    }
+
+    #[test]
+    fn test_random_datetime_within_bounds() {
+        let low = NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let high = NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let ca = random_datetime("t", 256, low, high, TimeUnit::Milliseconds, Some(7)).unwrap();
+        for v in raw(&ca) {
+            assert!(v >= low && v <= high);
+        }
+    }
+}